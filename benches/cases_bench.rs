@@ -0,0 +1,19 @@
+use datafile_test::datafile_bench;
+
+#[derive(Debug, serde::Deserialize)]
+struct CaseInput {
+    a: i32,
+    b: i32,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Case {
+    input: CaseInput,
+    output: i32,
+}
+
+// Only the two cases flagged `bench: true` are benchmarked; "not benched" is skipped.
+#[datafile_bench("benches/cases.json")]
+fn bench_sum(testcase: Case) {
+    assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
+}
@@ -2,18 +2,268 @@
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
+use std::collections::HashSet;
+use std::ffi::OsStr;
 use std::fs;
-use syn::{parse_macro_input, ItemFn, LitStr};
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Expr, ExprLit, ItemFn, Lit, LitStr, MetaNameValue, Token};
 
-/// Define data-file-driven tests using JSON/YAML files.
+/// Parsed form of the `datafile_test` attribute arguments.
 ///
-/// This attribute macro reads a JSON/YAML file at compile time and generates a test function for each
-/// test case in the file. The test function must take a single argument, which is a structured type
-/// that implements `serde::Deserialize`.
-/// The file is read from the file system relative to the current working directory of the
-/// compiler.
+/// Accepts either the original bare string form, `#[datafile_test("path.json")]`, or a
+/// key/value form that also allows an explicit format override:
+/// `#[datafile_test(path = "cases.txt", format = "toml")]`.
+struct DatafileTestArgs {
+    path: LitStr,
+    format: Option<String>,
+}
+
+impl Parse for DatafileTestArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Backward-compatible bare string form: #[datafile_test("path.json")]
+        let fork = input.fork();
+        if fork.parse::<LitStr>().is_ok() && fork.is_empty() {
+            let path = input.parse::<LitStr>()?;
+            return Ok(DatafileTestArgs { path, format: None });
+        }
+
+        let mut path = None;
+        let mut format = None;
+        for pair in Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)? {
+            let key = pair
+                .path
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            match key.as_str() {
+                "path" => path = Some(expect_lit_str(&pair.value)?),
+                "format" => format = Some(expect_lit_str(&pair.value)?.value()),
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        &pair.path,
+                        format!("unknown datafile_test option `{}`", other),
+                    ));
+                }
+            }
+        }
+
+        let path = path.ok_or_else(|| {
+            syn::Error::new(
+                input.span(),
+                "datafile_test requires a `path = \"...\"` option",
+            )
+        })?;
+        Ok(DatafileTestArgs { path, format })
+    }
+}
+
+/// Parsed form of the `datafile_bench` attribute arguments. Mirrors [`DatafileTestArgs`] with an
+/// additional `all` flag selecting whether every case (rather than only `bench: true` ones) is
+/// benchmarked.
+struct DatafileBenchArgs {
+    path: LitStr,
+    format: Option<String>,
+    all: bool,
+}
+
+impl Parse for DatafileBenchArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        if fork.parse::<LitStr>().is_ok() && fork.is_empty() {
+            let path = input.parse::<LitStr>()?;
+            return Ok(DatafileBenchArgs {
+                path,
+                format: None,
+                all: false,
+            });
+        }
+
+        let mut path = None;
+        let mut format = None;
+        let mut all = false;
+        for pair in Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)? {
+            let key = pair
+                .path
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            match key.as_str() {
+                "path" => path = Some(expect_lit_str(&pair.value)?),
+                "format" => format = Some(expect_lit_str(&pair.value)?.value()),
+                "all" => all = expect_lit_bool(&pair.value)?,
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        &pair.path,
+                        format!("unknown datafile_bench option `{}`", other),
+                    ));
+                }
+            }
+        }
+
+        let path = path.ok_or_else(|| {
+            syn::Error::new(
+                input.span(),
+                "datafile_bench requires a `path = \"...\"` option",
+            )
+        })?;
+        Ok(DatafileBenchArgs { path, format, all })
+    }
+}
+
+/// Extracts a string literal from a `key = "value"` attribute argument.
+fn expect_lit_str(expr: &Expr) -> syn::Result<LitStr> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => Ok(s.clone()),
+        other => Err(syn::Error::new_spanned(other, "expected a string literal")),
+    }
+}
+
+/// Extracts a bool literal from a `key = true` attribute argument.
+fn expect_lit_bool(expr: &Expr) -> syn::Result<bool> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(b), ..
+        }) => Ok(b.value),
+        other => Err(syn::Error::new_spanned(other, "expected a boolean literal")),
+    }
+}
+
+/// Where, within a parsed data file, a single case's value lives.
+enum CaseLocator {
+    /// The whole file is the case (per-file mode).
+    WholeFile,
+    /// The case at this index of a top-level array.
+    ArrayIndex(usize),
+}
+
+/// Per-case `should_panic`/`ignore` control directives, read from a case's JSON/YAML/TOML/RON
+/// object before it is deserialized into the user's test case type.
+#[derive(Default)]
+struct CaseDirectives {
+    /// `None` if the case has no `should_panic` key. `Some(None)` for a bare `should_panic:
+    /// true`. `Some(Some(expected))` for `should_panic: { expected = "..." }`.
+    should_panic: Option<Option<String>>,
+    ignore: bool,
+}
+
+/// Reads the `should_panic`/`ignore` control keys off a case, if it's a JSON/YAML/TOML/RON
+/// object. Non-object cases (and cases without these keys) get the default, empty directives.
+fn extract_directives(case: &serde_json::Value) -> CaseDirectives {
+    let obj = match case.as_object() {
+        Some(obj) => obj,
+        None => return CaseDirectives::default(),
+    };
+
+    let should_panic = match obj.get("should_panic") {
+        Some(serde_json::Value::Bool(true)) => Some(None),
+        Some(serde_json::Value::Object(opts)) => Some(
+            opts.get("expected")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+        ),
+        _ => None,
+    };
+    let ignore = obj.get("ignore").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    CaseDirectives {
+        should_panic,
+        ignore,
+    }
+}
+
+/// Renders a case's [`CaseDirectives`] as the `#[should_panic(...)]`/`#[ignore]` attributes to
+/// place on its generated test function.
+fn directive_attrs(directives: &CaseDirectives) -> proc_macro2::TokenStream {
+    let should_panic = match &directives.should_panic {
+        Some(Some(expected)) => quote! { #[should_panic(expected = #expected)] },
+        Some(None) => quote! { #[should_panic] },
+        None => quote! {},
+    };
+    let ignore = if directives.ignore {
+        quote! { #[ignore] }
+    } else {
+        quote! {}
+    };
+    quote! {
+        #should_panic
+        #ignore
+    }
+}
+
+/// Emits code removing the `should_panic`/`ignore` control keys from `__case` (if it's an
+/// object) so they don't leak into the user's deserialized test case type.
+fn strip_directive_keys_tokens() -> proc_macro2::TokenStream {
+    quote! {
+        let __case = match __case {
+            serde_json::Value::Object(mut __map) => {
+                __map.remove("should_panic");
+                __map.remove("ignore");
+                serde_json::Value::Object(__map)
+            }
+            other => other,
+        };
+    }
+}
+
+/// Define data-file-driven tests using JSON/YAML/TOML/RON files.
+///
+/// This attribute macro reads a data file at compile time (to determine its shape and the names
+/// of the generated tests) and generates a test function for each test case in the file. The
+/// test function takes a single argument (or two, for the shared-fixture suite described
+/// below), which is a structured type that implements
+/// `serde::Deserialize`. The path is resolved relative to `CARGO_MANIFEST_DIR`.
+///
+/// The path argument may also be a glob such as `"tests/cases/*.json"` or a directory. In that
+/// case each matched file is deserialized in its entirety into the test case type (rather than a
+/// single file containing a JSON/YAML array of cases), and one test is generated per file, named
+/// `{fn_name}_{file_stem}` (de-duplicated by appending the file's position if two stems sanitize
+/// to the same identifier). Matched files are sorted by path so generated test names are stable.
+/// A directory is scanned non-recursively and only files whose extension matches `format` (or,
+/// absent an override, one of `json`/`yaml`/`yml`/`toml`/`ron`) are treated as fixtures, so a
+/// stray `README.md` or `.gitkeep` alongside them is ignored.
 ///
-/// Note that `serde` and `serde_json` crate is required in caller's `Cargo.toml`.
+/// When a case in a JSON/YAML array carries a `name` or `description` string field, it is
+/// slugified into the generated test's identifier (e.g. `{fn_name}_adds_two_numbers`) instead of
+/// the numeric index. Cases without one, or whose slug collides with an earlier case, fall back
+/// to `{fn_name}_case_{i}` (or `{fn_name}_{slug}_{i}` on collision); that fallback name is itself
+/// de-duplicated against every earlier name if it still collides.
+///
+/// JSON, YAML, TOML and RON are all recognized by extension (`.json`, `.yaml`/`.yml`, `.toml`,
+/// `.ron`). To use an extension-less path or override the detected format, pass the key/value
+/// form instead of a bare string: `#[datafile_test(path = "cases.txt", format = "toml")]`.
+///
+/// TOML has no top-level array, so a TOML (or `format = "toml"`) array-of-cases file must wrap
+/// its cases in a table instead, `cases = [...]`, rather than being a bare array at the top
+/// level; JSON, YAML and RON may use either shape. This doesn't apply to per-file mode, where
+/// the whole file (of any format) is one case.
+///
+/// The test function may also take two arguments, `fn test(given: Given, case: Case)`, for a
+/// shared-fixture suite: the data file must then be a top-level object shaped
+/// `{ "given": ..., "cases": [...] }` rather than a bare array. `given` is deserialized once and
+/// paired with each deserialized element of `cases` to produce one test per case.
+///
+/// Each generated test embeds its data file via `include_str!` (so edits to the file are picked
+/// up by Cargo's normal rebuild tracking) and parses and deserializes it at test run time, rather
+/// than embedding a pre-serialized case in the expanded code. A deserialization failure panics
+/// with the source file, the case's index/name, and the underlying `serde` error.
+///
+/// A case object may also carry two control keys, read before the case is deserialized into the
+/// test case type (and stripped from it beforehand, so they never reach it): `should_panic`,
+/// either `true` or `{ expected = "substring" }`, generates a `#[should_panic]` or
+/// `#[should_panic(expected = "substring")]` attribute on that case's test; `ignore` (`true`)
+/// generates `#[ignore]`. This lets known-failing or flaky fixtures be marked directly in the
+/// data file instead of commented out or special-cased in the test body.
+///
+/// Note that `serde` and `serde_json` are required in caller's `Cargo.toml` for every fixture
+/// format, since cases are always deserialized through `serde_json::Value`. Because the runtime
+/// loader re-parses the source file itself (rather than only the macro parsing it at compile
+/// time), the crate matching the fixture's format is also required at the call site: `serde_yaml`
+/// for `.yaml`/`.yml`, `toml` for `.toml`, or `ron` for `.ron`. A future release should
+/// feature-gate these per format so a JSON-only caller doesn't need to depend on the others.
 ///
 /// # Example
 /// ```rust
@@ -53,8 +303,10 @@ use syn::{parse_macro_input, ItemFn, LitStr};
 #[proc_macro_attribute]
 pub fn datafile_test(attr: TokenStream, item: TokenStream) -> TokenStream {
     // Parse attribute
-    let attr = parse_macro_input!(attr as LitStr);
-    let file_path = attr.value();
+    let args = parse_macro_input!(attr as DatafileTestArgs);
+    let path_spec = args.path.value();
+    let format_override = args.format.as_deref();
+    let attr = args.path;
 
     // Parse the function item
     let input_fn = parse_macro_input!(item as ItemFn);
@@ -62,128 +314,809 @@ pub fn datafile_test(attr: TokenStream, item: TokenStream) -> TokenStream {
     let fn_body = &input_fn.block;
     let fn_args = &input_fn.sig.inputs;
 
-    // Ensure the function has exactly one argument
-    if fn_args.len() != 1 {
+    // A single argument is the classic one-case-per-test form; two arguments is a shared-fixture
+    // "given + cases" suite, where the first argument is deserialized once from a top-level
+    // `given` field and the second from each element of a top-level `cases` array.
+    if fn_args.len() != 1 && fn_args.len() != 2 {
         return syn::Error::new_spanned(
             &input_fn.sig,
-            "datafile_test function must have exactly one argument",
+            "datafile_test function must take one argument, or two for a given+cases suite",
         )
         .to_compile_error()
         .into();
     }
 
-    let test_case_type = match fn_args.first().unwrap() {
-        syn::FnArg::Typed(pat_type) => &pat_type.ty,
-        _ => {
-            return syn::Error::new_spanned(
-                &input_fn.sig,
-                "datafile_test function must take a structured argument",
-            )
-            .to_compile_error()
-            .into();
+    let mut arg_types = Vec::with_capacity(fn_args.len());
+    for arg in fn_args {
+        match arg {
+            syn::FnArg::Typed(pat_type) => arg_types.push(pat_type.ty.as_ref()),
+            _ => {
+                return syn::Error::new_spanned(
+                    &input_fn.sig,
+                    "datafile_test function must take structured arguments",
+                )
+                .to_compile_error()
+                .into();
+            }
         }
+    }
+    let test_case_type = arg_types[arg_types.len() - 1];
+    let given_type = if arg_types.len() == 2 {
+        Some(arg_types[0])
+    } else {
+        None
     };
 
-    // Load JSON/YAML file at compile time
-    let data_text = match fs::read_to_string(&file_path) {
-        Ok(content) => content,
+    // A glob or directory fans out into one test per matched file; a plain path keeps the
+    // original behaviour of one file containing an array of cases.
+    let per_file_mode = is_glob_pattern(&path_spec) || manifest_dir().join(&path_spec).is_dir();
+
+    let files = match resolve_data_files(&path_spec, format_override) {
+        Ok(files) if !files.is_empty() => files,
+        Ok(_) => {
+            return syn::Error::new_spanned(&attr, format!("No files matched '{}'", path_spec))
+                .to_compile_error()
+                .into();
+        }
         Err(e) => {
-            return syn::Error::new_spanned(
-                &attr,
-                format!("Failed to read data file '{:?}': {}", &file_path, e),
-            )
-            .to_compile_error()
-            .into();
+            return syn::Error::new_spanned(&attr, e).to_compile_error().into();
         }
     };
 
-    let ext = std::path::Path::new(&file_path)
-        .extension()
-        .and_then(std::ffi::OsStr::to_str)
-        .unwrap_or_default()
-        .to_lowercase();
-    // Parse JSON/YAML into Vec<serde_json::Value>
-    let test_cases: Vec<serde_json::Value> = match ext.as_str() {
-        "json" => match serde_json::from_str(&data_text) {
-            Ok(cases) => cases,
-            Err(e) => {
-                return syn::Error::new_spanned(
+    let test_fns: Vec<_> = if let Some(given_type) = given_type {
+        let mut fns = Vec::new();
+        for file in &files {
+            let value = match read_data_file(file, format_override) {
+                Ok(v) => v,
+                Err(e) => return syn::Error::new_spanned(&attr, e).to_compile_error().into(),
+            };
+            let obj = match value.as_object() {
+                Some(obj) if obj.contains_key("given") && obj.contains_key("cases") => obj,
+                _ => {
+                    return syn::Error::new_spanned(
+                        &attr,
+                        format!(
+                            "Data file '{:?}' must be an object with `given` and `cases` fields",
+                            file
+                        ),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            };
+            let cases = match &obj["cases"] {
+                serde_json::Value::Array(cases) => cases,
+                _ => {
+                    return syn::Error::new_spanned(
+                        &attr,
+                        format!("`cases` in '{:?}' must be an array", file),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            };
+
+            let rel_path = path_to_rel_str(file);
+            let format = detect_format(file, format_override);
+            let file_prefix = per_file_mode.then(|| {
+                sanitize_ident(file.file_stem().and_then(OsStr::to_str).unwrap_or_default())
+            });
+
+            let mut used_names: HashSet<String> = HashSet::new();
+            for (i, case) in cases.iter().enumerate() {
+                let slug = case
+                    .as_object()
+                    .and_then(|obj| obj.get("name").or_else(|| obj.get("description")))
+                    .and_then(|v| v.as_str())
+                    .map(slugify)
+                    .filter(|s| !s.is_empty());
+                let name = match slug {
+                    Some(slug) if used_names.insert(slug.clone()) => slug,
+                    Some(slug) => dedupe_name(&mut used_names, format!("{}_{}", slug, i)),
+                    None => dedupe_name(&mut used_names, format!("case_{}", i)),
+                };
+                let full_name = match &file_prefix {
+                    Some(prefix) => format!("{}_{}", prefix, name),
+                    None => name.clone(),
+                };
+                let test_fn_name = format_ident!("{}_{}", fn_name, full_name);
+                let directives = extract_directives(case);
+                fns.push(build_given_cases_test_fn(
+                    &test_fn_name,
+                    given_type,
+                    test_case_type,
+                    &rel_path,
+                    &format,
+                    i,
+                    &name,
+                    &directives,
+                    fn_body,
                     &attr,
-                    format!("Failed to parse JSON file '{:?}': {}", &file_path, e),
-                )
-                .to_compile_error()
-                .into();
+                ));
             }
-        },
-        "yaml" | "yml" => match serde_yaml::from_str(&data_text) {
-            Ok(cases) => cases,
-            Err(e) => {
-                return syn::Error::new_spanned(
+        }
+        fns
+    } else if per_file_mode {
+        let mut used_names: HashSet<String> = HashSet::new();
+        files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| {
+                // Read once to validate the file parses, pick a test name, and check for
+                // should_panic/ignore directives; the generated test itself embeds the file via
+                // `include_str!` and re-parses it at run time.
+                let value = match read_data_file(file, format_override) {
+                    Ok(value) => value,
+                    Err(e) => return syn::Error::new_spanned(&attr, e).to_compile_error(),
+                };
+                let rel_path = path_to_rel_str(file);
+                let format = detect_format(file, format_override);
+                let stem = file.file_stem().and_then(OsStr::to_str).unwrap_or_default();
+                let base_name = sanitize_ident(stem);
+                let name = if used_names.insert(base_name.clone()) {
+                    base_name
+                } else {
+                    format!("{}_{}", base_name, i)
+                };
+                used_names.insert(name.clone());
+                let test_fn_name = format_ident!("{}_{}", fn_name, name);
+                let directives = extract_directives(&value);
+                build_test_fn(
+                    &test_fn_name,
+                    test_case_type,
+                    &rel_path,
+                    &format,
+                    &CaseLocator::WholeFile,
+                    &name,
+                    &directives,
+                    fn_body,
                     &attr,
-                    format!("Failed to parse YAML file '{:?}': {}", &file_path, e),
                 )
-                .to_compile_error()
-                .into();
-            }
-        },
+            })
+            .collect()
+    } else {
+        let file = &files[0];
+        let test_cases =
+            match read_data_file(file, format_override).and_then(|v| extract_case_array(v, file)) {
+                Ok(cases) => cases,
+                Err(e) => return syn::Error::new_spanned(&attr, e).to_compile_error().into(),
+            };
+
+        let rel_path = path_to_rel_str(file);
+        let format = detect_format(file, format_override);
+
+        let mut used_names: HashSet<String> = HashSet::new();
+        test_cases
+            .iter()
+            .enumerate()
+            .map(|(i, test_case)| {
+                let slug = test_case
+                    .as_object()
+                    .and_then(|obj| obj.get("name").or_else(|| obj.get("description")))
+                    .and_then(|v| v.as_str())
+                    .map(slugify)
+                    .filter(|s| !s.is_empty());
+
+                let name = match slug {
+                    Some(slug) if used_names.insert(slug.clone()) => slug,
+                    Some(slug) => dedupe_name(&mut used_names, format!("{}_{}", slug, i)),
+                    None => dedupe_name(&mut used_names, format!("case_{}", i)),
+                };
+
+                let test_fn_name = format_ident!("{}_{}", fn_name, name);
+                let directives = extract_directives(test_case);
+                build_test_fn(
+                    &test_fn_name,
+                    test_case_type,
+                    &rel_path,
+                    &format,
+                    &CaseLocator::ArrayIndex(i),
+                    &name,
+                    &directives,
+                    fn_body,
+                    &attr,
+                )
+            })
+            .collect()
+    };
+
+    let output = quote! {
+        #(#test_fns)*
+    };
+
+    output.into()
+}
+
+/// Define data-file-driven Criterion benchmarks using JSON/YAML/TOML/RON files.
+///
+/// Accepts the same path argument as [`macro@datafile_test`] (a literal path, glob, or
+/// directory, optionally with an explicit `format = "..."` override), but generates Criterion
+/// benchmark functions instead of `#[test]` functions, plus a `criterion_group!`/
+/// `criterion_main!` harness entry covering all of them.
+///
+/// By default only cases whose object has a `bench: true` field are benchmarked; pass
+/// `all = true` to benchmark every case regardless. Each case is deserialized once outside the
+/// timed loop, and the function body then runs inside `b.iter(...)`.
+///
+/// Note that the `criterion`, `serde` and `serde_json` crates are required in caller's
+/// `Cargo.toml`, with this bench target configured as `harness = false`, plus `serde_yaml`/
+/// `toml`/`ron` for those fixture formats (see [`macro@datafile_test`] for why).
+///
+/// # Example
+/// ```rust,ignore
+/// use datafile_test::datafile_bench;
+///
+/// #[datafile_bench(path = "benches/cases.json", all = true)]
+/// fn bench_parse(testcase: Case) {
+///     parse(&testcase.input);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn datafile_bench(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as DatafileBenchArgs);
+    let path_spec = args.path.value();
+    let format_override = args.format.as_deref();
+    let attr = args.path;
+
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let fn_name = &input_fn.sig.ident;
+    let fn_body = &input_fn.block;
+    let fn_args = &input_fn.sig.inputs;
+
+    if fn_args.len() != 1 {
+        return syn::Error::new_spanned(
+            &input_fn.sig,
+            "datafile_bench function must have exactly one argument",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let test_case_type = match fn_args.first().unwrap() {
+        syn::FnArg::Typed(pat_type) => pat_type.ty.as_ref(),
         _ => {
             return syn::Error::new_spanned(
-                &attr,
-                format!("Unsupported file extension: {:?}", ext),
+                &input_fn.sig,
+                "datafile_bench function must take a structured argument",
             )
             .to_compile_error()
             .into();
         }
     };
 
-    // Generate test functions for each case
-    let test_fns: Vec<_> = test_cases
-        .iter()
-        .enumerate()
-        .map(|(i, test_case)| {
-            let test_fn_name = format_ident!("{}_case_{}", fn_name, i);
-
-            // Convert serde_yaml::Value to JSON string
-            let json_str = match serde_json::to_string(test_case) {
-                Ok(s) => s,
-                Err(e) => {
-                    return syn::Error::new_spanned(
-                        &attr,
-                        format!("Failed to convert test case to JSON: {}", e),
-                    )
-                    .to_compile_error();
-                }
-            };
+    let per_file_mode = is_glob_pattern(&path_spec) || manifest_dir().join(&path_spec).is_dir();
 
-            // Convert JSON string to Rust expression
-            let test_case_expr: syn::Expr = match syn::parse_str(&format!(
-                "serde_json::from_str::<{}>({:?}).unwrap()",
-                quote!(#test_case_type),
-                json_str
-            )) {
-                Ok(expr) => expr,
-                Err(e) => {
-                    return syn::Error::new_spanned(
-                        &attr,
-                        format!("Failed to parse test case JSON as Rust expression: {}", e),
-                    )
-                    .to_compile_error();
-                }
+    let files = match resolve_data_files(&path_spec, format_override) {
+        Ok(files) if !files.is_empty() => files,
+        Ok(_) => {
+            return syn::Error::new_spanned(&attr, format!("No files matched '{}'", path_spec))
+                .to_compile_error()
+                .into();
+        }
+        Err(e) => return syn::Error::new_spanned(&attr, e).to_compile_error().into(),
+    };
+
+    // Collect cases to benchmark, honouring the per-case `bench` flag unless `all`.
+    let mut cases: Vec<(String, String, String, CaseLocator)> = Vec::new();
+    if per_file_mode {
+        let mut used_names: HashSet<String> = HashSet::new();
+        for (i, file) in files.iter().enumerate() {
+            let value = match read_data_file(file, format_override) {
+                Ok(v) => v,
+                Err(e) => return syn::Error::new_spanned(&attr, e).to_compile_error().into(),
+            };
+            if args.all || is_bench_case(&value) {
+                let stem = file.file_stem().and_then(OsStr::to_str).unwrap_or_default();
+                let base_name = sanitize_ident(stem);
+                let name = if used_names.insert(base_name.clone()) {
+                    base_name
+                } else {
+                    format!("{}_{}", base_name, i)
+                };
+                used_names.insert(name.clone());
+                cases.push((
+                    name,
+                    path_to_rel_str(file),
+                    detect_format(file, format_override),
+                    CaseLocator::WholeFile,
+                ));
+            }
+        }
+    } else {
+        let file = &files[0];
+        let values =
+            match read_data_file(file, format_override).and_then(|v| extract_case_array(v, file)) {
+                Ok(values) => values,
+                Err(e) => return syn::Error::new_spanned(&attr, e).to_compile_error().into(),
             };
 
-            quote! {
-                #[test]
-                fn #test_fn_name() {
-                    let testcase: #test_case_type = #test_case_expr;
-                    #fn_body
-                }
+        let rel_path = path_to_rel_str(file);
+        let format = detect_format(file, format_override);
+
+        let mut used_names: HashSet<String> = HashSet::new();
+        for (i, value) in values.iter().enumerate() {
+            if !args.all && !is_bench_case(value) {
+                continue;
             }
+            let slug = value
+                .as_object()
+                .and_then(|obj| obj.get("name").or_else(|| obj.get("description")))
+                .and_then(|v| v.as_str())
+                .map(slugify)
+                .filter(|s| !s.is_empty());
+            let name = match slug {
+                Some(slug) if used_names.insert(slug.clone()) => slug,
+                Some(slug) => dedupe_name(&mut used_names, format!("{}_{}", slug, i)),
+                None => dedupe_name(&mut used_names, format!("case_{}", i)),
+            };
+            cases.push((
+                name,
+                rel_path.clone(),
+                format.clone(),
+                CaseLocator::ArrayIndex(i),
+            ));
+        }
+    }
+
+    if cases.is_empty() {
+        return syn::Error::new_spanned(
+            &attr,
+            "no cases selected for benchmarking (mark cases with `bench: true` or pass `all = true`)",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let bench_fn_names: Vec<_> = cases
+        .iter()
+        .map(|(name, ..)| format_ident!("{}_{}", fn_name, name))
+        .collect();
+
+    let bench_fns: Vec<_> = cases
+        .iter()
+        .zip(&bench_fn_names)
+        .map(|((name, rel_path, format, locator), bench_fn_name)| {
+            build_bench_fn(
+                bench_fn_name,
+                test_case_type,
+                rel_path,
+                format,
+                locator,
+                name,
+                fn_body,
+                &attr,
+            )
         })
         .collect();
 
+    let group_name = format_ident!("{}_benches", fn_name);
+
     let output = quote! {
-        #(#test_fns)*
+        #(#bench_fns)*
+
+        criterion::criterion_group!(#group_name, #(#bench_fn_names),*);
+        criterion::criterion_main!(#group_name);
     };
 
     output.into()
 }
+
+/// Directory the current crate is being compiled from, used to resolve data file paths the same
+/// way `include_str!`/`env!("CARGO_MANIFEST_DIR")` will in the generated code.
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string()))
+}
+
+/// Renders `file` (relative to the manifest dir) as a forward-slash string suitable for
+/// embedding in a generated `concat!(env!("CARGO_MANIFEST_DIR"), "/", ...)` path.
+fn path_to_rel_str(file: &Path) -> String {
+    file.to_string_lossy().replace('\\', "/")
+}
+
+/// Determines the parser format for `file`: `format_override` if given, else its extension.
+fn detect_format(file: &Path, format_override: Option<&str>) -> String {
+    match format_override {
+        Some(format) => format.to_lowercase(),
+        None => file
+            .extension()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default()
+            .to_lowercase(),
+    }
+}
+
+/// Reads `file` (relative to the manifest dir) and parses it into a `serde_json::Value`
+/// according to `format_override`/its extension. Used at macro-expansion time only, to validate
+/// the file and derive test names; the generated tests embed the file and re-parse it at run time.
+fn read_data_file(file: &Path, format_override: Option<&str>) -> Result<serde_json::Value, String> {
+    let abs_path = manifest_dir().join(file);
+    let data_text = fs::read_to_string(&abs_path)
+        .map_err(|e| format!("Failed to read data file '{:?}': {}", file, e))?;
+
+    let format = detect_format(file, format_override);
+    match format.as_str() {
+        "json" => serde_json::from_str(&data_text)
+            .map_err(|e| format!("Failed to parse JSON file '{:?}': {}", file, e)),
+        "yaml" | "yml" => serde_yaml::from_str(&data_text)
+            .map_err(|e| format!("Failed to parse YAML file '{:?}': {}", file, e)),
+        "toml" => toml::from_str(&data_text)
+            .map_err(|e| format!("Failed to parse TOML file '{:?}': {}", file, e)),
+        "ron" => ron::from_str(&data_text)
+            .map_err(|e| format!("Failed to parse RON file '{:?}': {}", file, e)),
+        _ => Err(format!("Unsupported file extension/format: {:?}", format)),
+    }
+}
+
+/// Extracts the top-level array of cases from a parsed data file. JSON, YAML and RON can all
+/// have a top-level array, so `value` itself is used in that case. TOML requires a top-level
+/// table, so an object with a `cases` array field is accepted too (e.g. `cases = [...]`).
+fn extract_case_array(
+    value: serde_json::Value,
+    file: &Path,
+) -> Result<Vec<serde_json::Value>, String> {
+    match value {
+        serde_json::Value::Array(cases) => Ok(cases),
+        serde_json::Value::Object(mut obj) => match obj.remove("cases") {
+            Some(serde_json::Value::Array(cases)) => Ok(cases),
+            _ => Err(format!(
+                "Data file '{:?}' must contain an array of test cases, or an object with a `cases` array field",
+                file
+            )),
+        },
+        _ => Err(format!(
+            "Data file '{:?}' must contain an array of test cases, or an object with a `cases` array field",
+            file
+        )),
+    }
+}
+
+/// Emits `let __value: serde_json::Value = ...;`, loading `rel_path` via `include_str!` (so
+/// Cargo tracks it as a dependency of the expanding crate) and parsing it per `format` at test
+/// run time. Panics with the source path and the `serde` error on failure.
+fn runtime_parse_block(
+    rel_path: &str,
+    format: &str,
+    attr: &LitStr,
+) -> Result<proc_macro2::TokenStream, proc_macro2::TokenStream> {
+    let parse_call = match format {
+        "json" => quote! { serde_json::from_str::<serde_json::Value>(__data) },
+        "yaml" | "yml" => quote! { serde_yaml::from_str::<serde_json::Value>(__data) },
+        "toml" => quote! { toml::from_str::<serde_json::Value>(__data) },
+        "ron" => quote! { ron::from_str::<serde_json::Value>(__data) },
+        other => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("Unsupported file extension/format: {:?}", other),
+            )
+            .to_compile_error());
+        }
+    };
+
+    Ok(quote! {
+        let __data: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #rel_path));
+        let __value: serde_json::Value = #parse_call
+            .unwrap_or_else(|e| panic!("Failed to parse data file {:?}: {}", #rel_path, e));
+    })
+}
+
+/// Emits `let __case: serde_json::Value = ...;`, picking `__value` itself or an element of its
+/// top-level array per `locator`, with a panic naming the file and case on a shape mismatch.
+fn case_extract_tokens(
+    rel_path: &str,
+    locator: &CaseLocator,
+    case_label: &str,
+) -> proc_macro2::TokenStream {
+    match locator {
+        CaseLocator::WholeFile => quote! {
+            let __case = __value;
+        },
+        CaseLocator::ArrayIndex(i) => quote! {
+            let __cases = match __value {
+                serde_json::Value::Array(cases) => cases,
+                serde_json::Value::Object(mut map) => match map.remove("cases") {
+                    Some(serde_json::Value::Array(cases)) => cases,
+                    _ => panic!(
+                        "{:?} must contain an array of test cases, or an object with a `cases` array field",
+                        #rel_path
+                    ),
+                },
+                _ => panic!(
+                    "{:?} must contain an array of test cases, or an object with a `cases` array field",
+                    #rel_path
+                ),
+            };
+            let __case = __cases
+                .into_iter()
+                .nth(#i)
+                .unwrap_or_else(|| panic!("{:?} has no case {:?} at index {}", #rel_path, #case_label, #i));
+        },
+    }
+}
+
+/// Builds a single Criterion benchmark function for one case.
+#[allow(clippy::too_many_arguments)]
+fn build_bench_fn(
+    bench_fn_name: &syn::Ident,
+    test_case_type: &syn::Type,
+    rel_path: &str,
+    format: &str,
+    locator: &CaseLocator,
+    case_label: &str,
+    fn_body: &syn::Block,
+    attr: &LitStr,
+) -> proc_macro2::TokenStream {
+    let parse_block = match runtime_parse_block(rel_path, format, attr) {
+        Ok(tokens) => tokens,
+        Err(err) => return err,
+    };
+    let extract = case_extract_tokens(rel_path, locator, case_label);
+    let bench_id = bench_fn_name.to_string();
+
+    quote! {
+        fn #bench_fn_name(c: &mut criterion::Criterion) {
+            #parse_block
+            #extract
+            let testcase: #test_case_type = serde_json::from_value(__case)
+                .unwrap_or_else(|e| panic!("Failed to deserialize case {:?} from {:?}: {}", #case_label, #rel_path, e));
+            c.bench_function(#bench_id, |b| {
+                b.iter(|| {
+                    #fn_body
+                });
+            });
+        }
+    }
+}
+
+/// Whether a case should be benchmarked by default: its object has a `bench: true` field.
+fn is_bench_case(value: &serde_json::Value) -> bool {
+    value
+        .as_object()
+        .and_then(|obj| obj.get("bench"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Builds the generated `#[test]` function for a single test case.
+#[allow(clippy::too_many_arguments)]
+fn build_test_fn(
+    test_fn_name: &syn::Ident,
+    test_case_type: &syn::Type,
+    rel_path: &str,
+    format: &str,
+    locator: &CaseLocator,
+    case_label: &str,
+    directives: &CaseDirectives,
+    fn_body: &syn::Block,
+    attr: &LitStr,
+) -> proc_macro2::TokenStream {
+    let parse_block = match runtime_parse_block(rel_path, format, attr) {
+        Ok(tokens) => tokens,
+        Err(err) => return err,
+    };
+    let extract = case_extract_tokens(rel_path, locator, case_label);
+    let strip_keys = strip_directive_keys_tokens();
+    let attrs = directive_attrs(directives);
+
+    quote! {
+        #attrs
+        #[test]
+        fn #test_fn_name() {
+            #parse_block
+            #extract
+            #strip_keys
+            let testcase: #test_case_type = serde_json::from_value(__case)
+                .unwrap_or_else(|e| panic!("Failed to deserialize case {:?} from {:?}: {}", #case_label, #rel_path, e));
+            #fn_body
+        }
+    }
+}
+
+/// Builds the generated `#[test]` function for a given+cases suite, binding the shared fixture
+/// as `given` and the individual case as `case`.
+#[allow(clippy::too_many_arguments)]
+fn build_given_cases_test_fn(
+    test_fn_name: &syn::Ident,
+    given_type: &syn::Type,
+    case_type: &syn::Type,
+    rel_path: &str,
+    format: &str,
+    case_index: usize,
+    case_label: &str,
+    directives: &CaseDirectives,
+    fn_body: &syn::Block,
+    attr: &LitStr,
+) -> proc_macro2::TokenStream {
+    let parse_block = match runtime_parse_block(rel_path, format, attr) {
+        Ok(tokens) => tokens,
+        Err(err) => return err,
+    };
+    let strip_keys = strip_directive_keys_tokens();
+    let attrs = directive_attrs(directives);
+
+    quote! {
+        #attrs
+        #[test]
+        fn #test_fn_name() {
+            #parse_block
+            let __obj = __value
+                .as_object()
+                .unwrap_or_else(|| panic!("{:?} must be an object with `given` and `cases` fields", #rel_path));
+            let __given = __obj
+                .get("given")
+                .unwrap_or_else(|| panic!("{:?} is missing a `given` field", #rel_path))
+                .clone();
+            let __case = __obj
+                .get("cases")
+                .and_then(|v| v.as_array())
+                .unwrap_or_else(|| panic!("{:?} must have a `cases` array", #rel_path))
+                .get(#case_index)
+                .unwrap_or_else(|| panic!("{:?} has no case {:?} at index {}", #rel_path, #case_label, #case_index))
+                .clone();
+            #strip_keys
+            let given: #given_type = serde_json::from_value(__given)
+                .unwrap_or_else(|e| panic!("Failed to deserialize `given` from {:?}: {}", #rel_path, e));
+            let case: #case_type = serde_json::from_value(__case)
+                .unwrap_or_else(|e| panic!("Failed to deserialize case {:?} ({:?}) from {:?}: {}", #case_label, #case_index, #rel_path, e));
+            #fn_body
+        }
+    }
+}
+
+/// Whether `path_spec` contains glob wildcard characters.
+fn is_glob_pattern(path_spec: &str) -> bool {
+    path_spec.contains('*') || path_spec.contains('?') || path_spec.contains('[')
+}
+
+/// File extensions recognized as data files when `format_override` isn't given.
+const KNOWN_EXTENSIONS: &[&str] = &["json", "yaml", "yml", "toml", "ron"];
+
+/// Whether `file`'s extension is a data file extension: `format_override` if given, else one of
+/// [`KNOWN_EXTENSIONS`]. Used to keep directory scans from picking up a stray `README.md` or
+/// `.gitkeep` alongside the fixtures.
+fn has_data_file_extension(file: &Path, format_override: Option<&str>) -> bool {
+    let ext = file
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_lowercase();
+    match format_override {
+        Some(format) => ext == format.to_lowercase(),
+        None => KNOWN_EXTENSIONS.contains(&ext.as_str()),
+    }
+}
+
+/// Resolves `path_spec` (relative to `CARGO_MANIFEST_DIR`) to a sorted list of files, also
+/// relative to `CARGO_MANIFEST_DIR`: the glob's matches, the directory's entries (filtered to
+/// `format_override`/[`KNOWN_EXTENSIONS`]), or the single literal path itself.
+fn resolve_data_files(
+    path_spec: &str,
+    format_override: Option<&str>,
+) -> Result<Vec<PathBuf>, String> {
+    let base = manifest_dir();
+    let abs_path = base.join(path_spec);
+
+    let mut files: Vec<PathBuf> = if is_glob_pattern(path_spec) {
+        let (dir, pattern) = match path_spec.rfind('/') {
+            Some(idx) => (base.join(&path_spec[..idx]), &path_spec[idx + 1..]),
+            None => (base.clone(), path_spec),
+        };
+        fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read directory '{:?}': {}", dir, e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|p| p.is_file())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(OsStr::to_str)
+                    .map(|name| glob_match(pattern, name))
+                    .unwrap_or(false)
+            })
+            .collect()
+    } else if abs_path.is_dir() {
+        fs::read_dir(&abs_path)
+            .map_err(|e| format!("Failed to read directory '{:?}': {}", abs_path, e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|p| p.is_file())
+            .filter(|p| has_data_file_extension(p, format_override))
+            .collect()
+    } else {
+        vec![abs_path]
+    };
+
+    files.sort();
+
+    // Store paths relative to the manifest dir so codegen can embed a portable path built from
+    // `CARGO_MANIFEST_DIR` at the call site.
+    Ok(files
+        .into_iter()
+        .map(|f| f.strip_prefix(&base).map(Path::to_path_buf).unwrap_or(f))
+        .collect())
+}
+
+/// Matches `name` against a simple glob `pattern` supporting `*` (any run of characters) and `?`
+/// (any single character) within one path segment.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    fn inner(pattern: &[u8], name: &[u8]) -> bool {
+        match (pattern.first(), name.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                inner(&pattern[1..], name) || (!name.is_empty() && inner(pattern, &name[1..]))
+            }
+            (Some(b'?'), Some(_)) => inner(&pattern[1..], &name[1..]),
+            (Some(p), Some(n)) if p == n => inner(&pattern[1..], &name[1..]),
+            _ => false,
+        }
+    }
+    inner(pattern.as_bytes(), name.as_bytes())
+}
+
+/// Slugifies a case's `name`/`description` field into a Rust identifier fragment, e.g.
+/// `"Adds two numbers"` becomes `"adds_two_numbers"`.
+fn slugify(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut prev_underscore = false;
+    for c in raw.chars() {
+        let c = c.to_ascii_lowercase();
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+            prev_underscore = false;
+        } else if !prev_underscore {
+            out.push('_');
+            prev_underscore = true;
+        }
+    }
+    let trimmed = out.trim_matches('_').to_string();
+    if trimmed
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(true)
+    {
+        format!("_{}", trimmed)
+    } else {
+        trimmed
+    }
+}
+
+/// Inserts `base` into `used_names`, returning it unchanged if it wasn't already present, or
+/// else appending `_1`, `_2`, ... until a free name is found. Used for the `slug_{i}`/`case_{i}`
+/// fallback names, which can themselves collide with an earlier case (e.g. a case literally
+/// named "case 1" followed by a nameless case at index 1 would both slugify to `case_1`).
+fn dedupe_name(used_names: &mut HashSet<String>, base: String) -> String {
+    if used_names.insert(base.clone()) {
+        return base;
+    }
+    let mut n = 1;
+    loop {
+        let candidate = format!("{}_{}", base, n);
+        if used_names.insert(candidate.clone()) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Turns an arbitrary file stem into a valid Rust identifier fragment by replacing
+/// non-identifier characters with `_`.
+fn sanitize_ident(raw: &str) -> String {
+    let mut out: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if out
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(true)
+    {
+        out.insert(0, '_');
+    }
+    out
+}
@@ -23,3 +23,78 @@ fn test_with_json(testcase: TestCase) {
 fn test_with_yaml(testcase: TestCase) {
     assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
 }
+
+// Directory mode: one test per file in `tests/glob_cases/`, including two files (`a-b.json`,
+// `a.b.json`) whose sanitized stems collide and must be de-duplicated, and a `README.md` that
+// must be skipped rather than tried as a fixture.
+#[datafile_test("tests/glob_cases")]
+fn test_with_glob_dir(testcase: TestCase) {
+    assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
+}
+
+// Explicit glob pattern, narrowed to the colliding pair to exercise the glob branch directly.
+#[datafile_test("tests/glob_cases/a*.json")]
+fn test_with_glob_pattern(testcase: TestCase) {
+    assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
+}
+
+// Two cases share the `name` "adds positive numbers" (de-duplicated via the index suffix) and
+// one has neither `name` nor `description` (falls back to `case_N`).
+#[datafile_test("tests/named_cases.json")]
+fn test_named_cases(testcase: TestCase) {
+    assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
+}
+
+// TOML has no top-level array, so `tests/cases.toml` wraps its cases in a `cases` table.
+#[datafile_test("tests/cases.toml")]
+fn test_with_toml(testcase: TestCase) {
+    assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
+}
+
+#[datafile_test("tests/cases.ron")]
+fn test_with_ron(testcase: TestCase) {
+    assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Given {
+    base: i32,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DeltaCase {
+    delta: i32,
+    expected: i32,
+}
+
+// Shared-fixture suite: `given.base` is deserialized once and paired with each of `cases`.
+#[datafile_test("tests/given_cases.json")]
+fn test_given_cases(given: Given, case: DeltaCase) {
+    assert_eq!(given.base + case.delta, case.expected);
+}
+
+// Exercises `include_str!`/`CARGO_MANIFEST_DIR`-relative resolution through a nested directory,
+// where the runtime loader re-parses the embedded `tests/nested/deep.json` at test run time.
+#[datafile_test("tests/nested/deep.json")]
+fn test_nested_path(testcase: TestCase) {
+    assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
+}
+
+// `deny_unknown_fields` proves `should_panic`/`ignore` are stripped before deserializing: if they
+// weren't, the two cases carrying one of those keys would fail to deserialize with an
+// `unknown field` error instead of running. The cases have no `name`/`description` (those aren't
+// stripped, so a deny_unknown_fields type can't carry one) and so are named `case_0`/`case_1`/
+// `case_2`.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StrictTestCase {
+    input: TestCaseInput,
+    output: i32,
+}
+
+// `case_1` is `should_panic: { expected = "assertion" }` (the body's `assert_eq!` panics with
+// that substring); `case_2` is `ignore: true` and doesn't run by default.
+#[datafile_test("tests/should_panic_cases.json")]
+fn test_should_panic_and_ignore(testcase: StrictTestCase) {
+    assert_eq!(testcase.input.a + testcase.input.b, testcase.output);
+}